@@ -0,0 +1,186 @@
+// Copyright 2019 Google LLC
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use core::alloc::Layout;
+use core::any::{type_name, TypeId};
+use core::ptr::NonNull;
+
+use crate::borrow::AtomicBorrow;
+use crate::Component;
+
+/// Metadata describing one of an archetype's component columns
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct TypeInfo {
+    id: TypeId,
+    layout: Layout,
+}
+
+impl TypeInfo {
+    pub fn of<T: Component>() -> Self {
+        Self {
+            id: TypeId::of::<T>(),
+            layout: Layout::new::<T>(),
+        }
+    }
+
+    pub fn id(&self) -> TypeId {
+        self.id
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+}
+
+/// A collection of entities having the same component types, stored in parallel columns
+///
+/// `types`, `borrows` and `bases` are kept index-aligned: column `i`'s metadata, borrow flag and
+/// storage base pointer all live at index `i` in their respective arrays.
+pub struct Archetype {
+    types: Box<[TypeInfo]>,
+    borrows: Box<[AtomicBorrow]>,
+    bases: Box<[NonNull<u8>]>,
+}
+
+impl Archetype {
+    fn column_index(&self, ty: TypeId) -> Option<usize> {
+        self.types.iter().position(|info| info.id() == ty)
+    }
+
+    /// The types of the components stored in this archetype
+    pub fn types(&self) -> &[TypeInfo] {
+        &self.types
+    }
+
+    /// Base pointer of the `T` column, if this archetype has one
+    pub fn get_base<T: Component>(&self) -> Option<NonNull<T>> {
+        let index = self.column_index(TypeId::of::<T>())?;
+        Some(self.bases[index].cast())
+    }
+
+    /// Acquire a shared borrow of the `T` column, panicking if it's already uniquely borrowed
+    ///
+    /// Most callers should prefer [`try_borrow`](Self::try_borrow), which reports contention as a
+    /// `bool` instead of unwinding.
+    pub fn borrow<T: Component>(&self) {
+        if !self.try_borrow::<T>() {
+            panic!("{} already uniquely borrowed", type_name::<T>());
+        }
+    }
+
+    /// Acquire a unique borrow of the `T` column, panicking if it's already borrowed
+    ///
+    /// Most callers should prefer [`try_borrow_mut`](Self::try_borrow_mut), which reports
+    /// contention as a `bool` instead of panicking.
+    pub fn borrow_mut<T: Component>(&self) {
+        if !self.try_borrow_mut::<T>() {
+            panic!("{} already borrowed", type_name::<T>());
+        }
+    }
+
+    /// Like [`borrow`](Self::borrow), but reports a conflicting borrow as `false` instead of
+    /// panicking
+    pub fn try_borrow<T: Component>(&self) -> bool {
+        match self.column_index(TypeId::of::<T>()) {
+            Some(index) => self.borrows[index].borrow(),
+            None => true,
+        }
+    }
+
+    /// Like [`borrow_mut`](Self::borrow_mut), but reports a conflicting borrow as `false` instead
+    /// of panicking
+    pub fn try_borrow_mut<T: Component>(&self) -> bool {
+        match self.column_index(TypeId::of::<T>()) {
+            Some(index) => self.borrows[index].borrow_mut(),
+            None => true,
+        }
+    }
+
+    pub fn release<T: Component>(&self) {
+        if let Some(index) = self.column_index(TypeId::of::<T>()) {
+            self.borrows[index].release();
+        }
+    }
+
+    pub fn release_mut<T: Component>(&self) {
+        if let Some(index) = self.column_index(TypeId::of::<T>()) {
+            self.borrows[index].release_mut();
+        }
+    }
+
+    /// Layout of the column holding components of type `ty`, if this archetype has one
+    ///
+    /// Lets callers that only know a component's `TypeId` (e.g. [`DynRef`](crate::borrow::DynRef))
+    /// compute byte offsets into the column without static type information.
+    pub fn layout(&self, ty: TypeId) -> Option<Layout> {
+        self.column_index(ty).map(|index| self.types[index].layout())
+    }
+
+    /// Pointer to the `index`th component of type `ty`, if this archetype has that column
+    ///
+    /// `size` must equal `self.layout(ty).unwrap().size()`; it's taken as a parameter so callers
+    /// that already fetched the layout don't pay for a second lookup.
+    ///
+    /// # Safety
+    ///
+    /// `size` must equal the `ty` column's component size (as returned by
+    /// [`layout`](Self::layout)), and `index` must be less than the number of entities stored in
+    /// this archetype. Violating either lets the returned pointer, if dereferenced, read or write
+    /// out of bounds of the column's allocation.
+    pub unsafe fn get_dynamic(&self, ty: TypeId, size: usize, index: u32) -> Option<NonNull<u8>> {
+        let column = self.column_index(ty)?;
+        debug_assert_eq!(size, self.types[column].layout().size());
+        Some(NonNull::new_unchecked(
+            self.bases[column].as_ptr().add(index as usize * size),
+        ))
+    }
+
+    /// Type-erased equivalent of [`try_borrow`](Self::try_borrow), keyed by `TypeId` instead of a
+    /// static type parameter
+    ///
+    /// Unlike `try_borrow`, returns `false` if this archetype has no `ty` column.
+    pub fn borrow_dynamic(&self, ty: TypeId) -> bool {
+        self.column_index(ty)
+            .is_some_and(|index| self.borrows[index].borrow())
+    }
+
+    /// Type-erased equivalent of [`release`](Self::release)
+    pub fn release_dynamic(&self, ty: TypeId) {
+        if let Some(index) = self.column_index(ty) {
+            self.borrows[index].release();
+        }
+    }
+
+    /// Type-erased equivalent of [`try_borrow_mut`](Self::try_borrow_mut)
+    ///
+    /// Unlike `try_borrow_mut`, returns `false` if this archetype has no `ty` column.
+    pub fn borrow_dynamic_mut(&self, ty: TypeId) -> bool {
+        self.column_index(ty)
+            .is_some_and(|index| self.borrows[index].borrow_mut())
+    }
+
+    /// Type-erased equivalent of [`release_mut`](Self::release_mut)
+    pub fn release_dynamic_mut(&self, ty: TypeId) {
+        if let Some(index) = self.column_index(ty) {
+            self.borrows[index].release_mut();
+        }
+    }
+}
+
+#[cfg(test)]
+impl Archetype {
+    /// Build a single-entity archetype holding just `value`, for unit tests elsewhere in the
+    /// crate that need a real `Archetype` to borrow from
+    pub(crate) fn for_test<T: Component>(value: T) -> Self {
+        let base = unsafe { NonNull::new_unchecked(Box::into_raw(Box::new(value)) as *mut u8) };
+        Self {
+            types: Box::new([TypeInfo::of::<T>()]),
+            borrows: Box::new([AtomicBorrow::new()]),
+            bases: Box::new([base]),
+        }
+    }
+}
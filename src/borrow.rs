@@ -5,13 +5,60 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use core::any::TypeId;
+use core::alloc::Layout;
+use core::any::{type_name, TypeId};
+use core::fmt;
 use core::ops::{Deref, DerefMut};
 use core::ptr::NonNull;
 use core::sync::atomic::{AtomicUsize, Ordering};
 
+use hashbrown::HashMap;
+
 use crate::archetype::Archetype;
-use crate::{Component, MissingComponent};
+use crate::Component;
+
+/// Error indicating that a component could not be borrowed from an entity
+///
+/// `EntityRef` is only ever constructed for an entity that's known to exist, so there's no
+/// "no such entity" case here; that belongs to whatever World-level lookup produces the
+/// `EntityRef` in the first place.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ComponentError {
+    /// The entity does not have a component of this type
+    MissingComponent {
+        /// Name of the missing component's type
+        type_name: &'static str,
+    },
+    /// The component is already borrowed in a way that conflicts with the request
+    CannotBorrow {
+        /// Name of the contended component's type
+        type_name: &'static str,
+    },
+    /// The component is opted into a [`GlobalBorrow`] associated with this entity, so it's only
+    /// reachable through [`try_get_with`](EntityRef::try_get_with)/
+    /// [`try_get_mut_with`](EntityRef::try_get_mut_with)
+    ManagedByGlobalBorrow {
+        /// Name of the globally-managed component's type
+        type_name: &'static str,
+    },
+}
+
+impl fmt::Display for ComponentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingComponent { type_name } => write!(f, "missing {} component", type_name),
+            Self::CannotBorrow { type_name } => write!(f, "{} already borrowed", type_name),
+            Self::ManagedByGlobalBorrow { type_name } => write!(
+                f,
+                "{} is managed by a GlobalBorrow; use try_get_with/try_get_mut_with",
+                type_name
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ComponentError {}
 
 pub struct AtomicBorrow(AtomicUsize);
 
@@ -54,27 +101,145 @@ impl AtomicBorrow {
 
 const UNIQUE_BIT: usize = !(usize::max_value() >> 1);
 
+/// World-level borrow tracking with one [`AtomicBorrow`] per component type, rather than one per
+/// archetype
+///
+/// Borrowing a component through a [`World`](crate::World) normally transitions an
+/// `AtomicBorrow` owned by whichever archetype the entity lives in, so a query spanning `N`
+/// archetypes performs `N` independent borrow transitions. Opting an entity's accesses into a
+/// `GlobalBorrow` instead tracks a single flag per [`TypeId`], shared by every archetype, so a
+/// query only ever performs one shared or unique transition per component type no matter how
+/// many archetypes it touches.
+///
+/// This is coarser: [`try_get_mut_with::<T>`](EntityRef::try_get_mut_with) on one entity will
+/// conflict with an outstanding borrow of `T` on a *different* entity, even one living in an
+/// unrelated archetype. Use it only where that tradeoff is acceptable.
+///
+/// A type registered here is governed *exclusively* by this flag for any [`EntityRef`]
+/// constructed with this `GlobalBorrow` attached: [`get`](EntityRef::get)/
+/// [`get_mut`](EntityRef::get_mut)/[`take_scoped`](EntityRef::take_scoped) panic for such a type
+/// rather than quietly transitioning the archetype's own, unrelated flag. This is what makes the
+/// two borrow-tracking schemes safe to use side by side — without it, a plain `get_mut` and a
+/// [`try_get_mut_with`](EntityRef::try_get_mut_with) could both succeed for the same component at
+/// once, handing out two live `&mut` references to the same memory.
+#[derive(Default)]
+pub struct GlobalBorrow {
+    flags: HashMap<TypeId, AtomicBorrow>,
+}
+
+impl GlobalBorrow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ensure a flag exists for `T`, so that later borrows never need to allocate
+    pub fn register<T: Component>(&mut self) {
+        self.flags
+            .entry(TypeId::of::<T>())
+            .or_insert_with(AtomicBorrow::new);
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `T` was never passed to [`register`](Self::register) on this `GlobalBorrow`.
+    /// There's no lazy fallback: inserting on first use would require mutating, and so
+    /// synchronizing, the `flags` map itself from behind a shared reference; registering up
+    /// front keeps every borrow a read-only lookup.
+    fn get_flag<T: Component>(&self) -> &AtomicBorrow {
+        self.flags
+            .get(&TypeId::of::<T>())
+            .expect("component type not registered with this GlobalBorrow")
+    }
+
+    /// Whether `T` has been [`register`](Self::register)ed with this `GlobalBorrow`
+    ///
+    /// An [`EntityRef`] associated with this `GlobalBorrow` uses this to route every access to a
+    /// registered type through the global flag exclusively, so it's never possible to hold a
+    /// borrow against the archetype's own flag and a [`GlobalBorrow`] flag at the same time for
+    /// the same component.
+    fn is_registered<T: Component>(&self) -> bool {
+        self.flags.contains_key(&TypeId::of::<T>())
+    }
+}
+
+/// Where a [`Ref`]/[`RefMut`] releases its borrow on drop
+#[derive(Clone)]
+enum BorrowSource<'a> {
+    Archetype(&'a Archetype),
+    Global(&'a AtomicBorrow),
+}
+
+impl<'a> BorrowSource<'a> {
+    unsafe fn release<T: Component>(&self) {
+        match self {
+            BorrowSource::Archetype(archetype) => archetype.release::<T>(),
+            BorrowSource::Global(flag) => flag.release(),
+        }
+    }
+
+    unsafe fn release_mut<T: Component>(&self) {
+        match self {
+            BorrowSource::Archetype(archetype) => archetype.release_mut::<T>(),
+            BorrowSource::Global(flag) => flag.release_mut(),
+        }
+    }
+}
+
+/// Locate the `T` component at `index` within `archetype`, without acquiring any borrow
+unsafe fn locate<T: Component>(
+    archetype: &Archetype,
+    index: u32,
+) -> Result<NonNull<T>, ComponentError> {
+    Ok(NonNull::new_unchecked(
+        archetype
+            .get_base::<T>()
+            .ok_or_else(|| ComponentError::MissingComponent {
+                type_name: type_name::<T>(),
+            })?
+            .as_ptr()
+            .add(index as usize),
+    ))
+}
+
 /// Shared borrow of an entity's component
 #[derive(Clone)]
 pub struct Ref<'a, T: Component> {
-    archetype: &'a Archetype,
     target: NonNull<T>,
+    source: BorrowSource<'a>,
 }
 
 impl<'a, T: Component> Ref<'a, T> {
-    pub(crate) unsafe fn new(
+    pub(crate) unsafe fn new(archetype: &'a Archetype, index: u32) -> Result<Self, ComponentError> {
+        let target = locate::<T>(archetype, index)?;
+        if !archetype.try_borrow::<T>() {
+            return Err(ComponentError::CannotBorrow {
+                type_name: type_name::<T>(),
+            });
+        }
+        Ok(Self {
+            target,
+            source: BorrowSource::Archetype(archetype),
+        })
+    }
+
+    /// Like [`new`](Self::new), but transitions a [`GlobalBorrow`]'s per-type flag instead of the
+    /// archetype's own, so the borrow is visible to every archetype that shares the flag
+    pub(crate) unsafe fn new_global(
+        global: &'a GlobalBorrow,
         archetype: &'a Archetype,
         index: u32,
-    ) -> Result<Self, MissingComponent> {
-        let target = NonNull::new_unchecked(
-            archetype
-                .get_base::<T>()
-                .ok_or_else(MissingComponent::new::<T>)?
-                .as_ptr()
-                .add(index as usize),
-        );
-        archetype.borrow::<T>();
-        Ok(Self { archetype, target })
+    ) -> Result<Self, ComponentError> {
+        let target = locate::<T>(archetype, index)?;
+        let flag = global.get_flag::<T>();
+        if !flag.borrow() {
+            return Err(ComponentError::CannotBorrow {
+                type_name: type_name::<T>(),
+            });
+        }
+        Ok(Self {
+            target,
+            source: BorrowSource::Global(flag),
+        })
     }
 }
 
@@ -83,7 +248,7 @@ unsafe impl<T: Component> Sync for Ref<'_, T> {}
 
 impl<'a, T: Component> Drop for Ref<'a, T> {
     fn drop(&mut self) {
-        self.archetype.release::<T>();
+        unsafe { self.source.release::<T>() }
     }
 }
 
@@ -96,24 +261,42 @@ impl<'a, T: Component> Deref for Ref<'a, T> {
 
 /// Unique borrow of an entity's component
 pub struct RefMut<'a, T: Component> {
-    archetype: &'a Archetype,
     target: NonNull<T>,
+    source: BorrowSource<'a>,
 }
 
 impl<'a, T: Component> RefMut<'a, T> {
-    pub(crate) unsafe fn new(
+    pub(crate) unsafe fn new(archetype: &'a Archetype, index: u32) -> Result<Self, ComponentError> {
+        let target = locate::<T>(archetype, index)?;
+        if !archetype.try_borrow_mut::<T>() {
+            return Err(ComponentError::CannotBorrow {
+                type_name: type_name::<T>(),
+            });
+        }
+        Ok(Self {
+            target,
+            source: BorrowSource::Archetype(archetype),
+        })
+    }
+
+    /// Like [`new`](Self::new), but transitions a [`GlobalBorrow`]'s per-type flag instead of the
+    /// archetype's own, so the borrow is visible to every archetype that shares the flag
+    pub(crate) unsafe fn new_global(
+        global: &'a GlobalBorrow,
         archetype: &'a Archetype,
         index: u32,
-    ) -> Result<Self, MissingComponent> {
-        let target = NonNull::new_unchecked(
-            archetype
-                .get_base::<T>()
-                .ok_or_else(MissingComponent::new::<T>)?
-                .as_ptr()
-                .add(index as usize),
-        );
-        archetype.borrow_mut::<T>();
-        Ok(Self { archetype, target })
+    ) -> Result<Self, ComponentError> {
+        let target = locate::<T>(archetype, index)?;
+        let flag = global.get_flag::<T>();
+        if !flag.borrow_mut() {
+            return Err(ComponentError::CannotBorrow {
+                type_name: type_name::<T>(),
+            });
+        }
+        Ok(Self {
+            target,
+            source: BorrowSource::Global(flag),
+        })
     }
 }
 
@@ -122,7 +305,7 @@ unsafe impl<T: Component> Sync for RefMut<'_, T> {}
 
 impl<'a, T: Component> Drop for RefMut<'a, T> {
     fn drop(&mut self) {
-        self.archetype.release_mut::<T>();
+        unsafe { self.source.release_mut::<T>() }
     }
 }
 
@@ -139,11 +322,199 @@ impl<'a, T: Component> DerefMut for RefMut<'a, T> {
     }
 }
 
+/// Type-erased shared borrow of a component, for when the concrete type isn't known at the call
+/// site
+///
+/// Obtained from [`EntityRef::get_dyn`]. Releases the same `AtomicBorrow` a typed [`Ref`] would
+/// on drop.
+pub struct DynRef<'a> {
+    target: NonNull<u8>,
+    layout: Layout,
+    archetype: &'a Archetype,
+    type_id: TypeId,
+}
+
+impl<'a> DynRef<'a> {
+    pub(crate) unsafe fn new(archetype: &'a Archetype, type_id: TypeId, index: u32) -> Option<Self> {
+        let layout = archetype.layout(type_id)?;
+        let target = archetype.get_dynamic(type_id, layout.size(), index)?;
+        if !archetype.borrow_dynamic(type_id) {
+            return None;
+        }
+        Some(Self {
+            target,
+            layout,
+            archetype,
+            type_id,
+        })
+    }
+
+    /// Pointer to the component's bytes
+    pub fn as_ptr(&self) -> NonNull<u8> {
+        self.target
+    }
+
+    /// Layout of the component's bytes
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+}
+
+impl Drop for DynRef<'_> {
+    fn drop(&mut self) {
+        self.archetype.release_dynamic(self.type_id)
+    }
+}
+
+/// Type-erased unique borrow of a component, for when the concrete type isn't known at the call
+/// site
+///
+/// Obtained from [`EntityRef::get_dyn_mut`]. Releases the same `AtomicBorrow` a typed [`RefMut`]
+/// would on drop.
+pub struct DynRefMut<'a> {
+    target: NonNull<u8>,
+    layout: Layout,
+    archetype: &'a Archetype,
+    type_id: TypeId,
+}
+
+impl<'a> DynRefMut<'a> {
+    pub(crate) unsafe fn new(archetype: &'a Archetype, type_id: TypeId, index: u32) -> Option<Self> {
+        let layout = archetype.layout(type_id)?;
+        let target = archetype.get_dynamic(type_id, layout.size(), index)?;
+        if !archetype.borrow_dynamic_mut(type_id) {
+            return None;
+        }
+        Some(Self {
+            target,
+            layout,
+            archetype,
+            type_id,
+        })
+    }
+
+    /// Pointer to the component's bytes
+    pub fn as_ptr(&self) -> NonNull<u8> {
+        self.target
+    }
+
+    /// Layout of the component's bytes
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+}
+
+impl Drop for DynRefMut<'_> {
+    fn drop(&mut self) {
+        self.archetype.release_dynamic_mut(self.type_id)
+    }
+}
+
+/// Maps component `TypeId`s to user-supplied, type-erased callbacks
+///
+/// Pairs with [`EntityRef::get_dyn`]/[`get_dyn_mut`](EntityRef::get_dyn_mut) to let code iterate
+/// [`EntityRef::component_types`] and invoke a per-component callback — a serializer, cloner, or
+/// debug-printer — without a compile-time list of every component type in the world. This is the
+/// `HashMap<TypeId, Box<dyn Handler>>` pattern `component_types`'s doc comment already gestures
+/// at, made concrete for save/load and editor inspectors.
+pub struct ComponentRegistry<F> {
+    handlers: HashMap<TypeId, F>,
+}
+
+impl<F> Default for ComponentRegistry<F> {
+    fn default() -> Self {
+        Self {
+            handlers: HashMap::new(),
+        }
+    }
+}
+
+impl<F> ComponentRegistry<F> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a handler for component type `T`, replacing any previous handler for it
+    pub fn register<T: Component>(&mut self, handler: F) {
+        self.handlers.insert(TypeId::of::<T>(), handler);
+    }
+
+    /// Look up the handler registered for `ty`, if any
+    pub fn get(&self, ty: TypeId) -> Option<&F> {
+        self.handlers.get(&ty)
+    }
+}
+
+/// Guard that moves a component out of an entity by value, restoring it on drop
+///
+/// Obtained from [`EntityRef::take_scoped`]. Acquires a unique borrow and `ptr::read`s the
+/// component's value out of its slot, leaving the caller free to own, mutate, or
+/// `std::mem::replace` it through `Deref`/`DerefMut` — safer than [`get_mut`](EntityRef::get_mut)
+/// for operations that need to temporarily own a component, such as passing it by value into a
+/// state-machine transition that returns a new value.
+///
+/// The slot is restored on drop, even if dropped during a panic's unwind: the taken value lives
+/// in `TakenRef` itself for the guard's whole lifetime, so whatever it holds (replaced or not) is
+/// written back before the unique borrow is released, and the slot is never left uninitialized.
+pub struct TakenRef<'a, T: Component> {
+    target: NonNull<T>,
+    value: core::mem::ManuallyDrop<T>,
+    archetype: &'a Archetype,
+}
+
+impl<'a, T: Component> TakenRef<'a, T> {
+    pub(crate) unsafe fn new(archetype: &'a Archetype, index: u32) -> Result<Self, ComponentError> {
+        let target = locate::<T>(archetype, index)?;
+        if !archetype.try_borrow_mut::<T>() {
+            return Err(ComponentError::CannotBorrow {
+                type_name: type_name::<T>(),
+            });
+        }
+        let value = core::mem::ManuallyDrop::new(target.as_ptr().read());
+        Ok(Self {
+            target,
+            value,
+            archetype,
+        })
+    }
+}
+
+unsafe impl<T: Component> Send for TakenRef<'_, T> {}
+unsafe impl<T: Component> Sync for TakenRef<'_, T> {}
+
+impl<T: Component> Deref for TakenRef<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Component> DerefMut for TakenRef<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: Component> Drop for TakenRef<'_, T> {
+    fn drop(&mut self) {
+        unsafe {
+            // Safety: `target` has been uninitialized since `new` read out of it; writing the
+            // (possibly replaced) held value back makes it valid again before anything else can
+            // observe the slot, whether this runs on the normal path or during an unwind.
+            self.target
+                .as_ptr()
+                .write(core::mem::ManuallyDrop::take(&mut self.value));
+            self.archetype.release_mut::<T>();
+        }
+    }
+}
+
 /// Handle to an entity with any component types
 #[derive(Copy, Clone)]
 pub struct EntityRef<'a> {
     archetype: Option<&'a Archetype>,
     index: u32,
+    global: Option<&'a GlobalBorrow>,
 }
 
 impl<'a> EntityRef<'a> {
@@ -152,6 +523,7 @@ impl<'a> EntityRef<'a> {
         Self {
             archetype: None,
             index: 0,
+            global: None,
         }
     }
 
@@ -159,22 +531,216 @@ impl<'a> EntityRef<'a> {
         Self {
             archetype: Some(archetype),
             index,
+            global: None,
+        }
+    }
+
+    /// Like [`new`](Self::new), but associates `global` as the single [`GlobalBorrow`] this
+    /// entity's globally-managed component types go through
+    ///
+    /// Any type [`register`](GlobalBorrow::register)ed with `global` becomes reachable only via
+    /// [`try_get_with`](Self::try_get_with)/[`try_get_mut_with`](Self::try_get_mut_with):
+    /// [`get`](Self::get)/[`get_mut`](Self::get_mut)/[`take_scoped`](Self::take_scoped) panic for
+    /// it instead of transitioning the archetype's own flag, which would let the two borrow
+    /// mechanisms alias the same memory.
+    pub(crate) unsafe fn new_with_global(
+        archetype: &'a Archetype,
+        index: u32,
+        global: &'a GlobalBorrow,
+    ) -> Self {
+        Self {
+            archetype: Some(archetype),
+            index,
+            global: Some(global),
+        }
+    }
+
+    /// `Err` if `T` is registered with this entity's associated [`GlobalBorrow`], in which case
+    /// only [`try_get_with`](Self::try_get_with)/[`try_get_mut_with`](Self::try_get_mut_with) may
+    /// access it
+    fn check_not_globally_managed<T: Component>(&self) -> Result<(), ComponentError> {
+        match self.global {
+            Some(global) if global.is_registered::<T>() => {
+                Err(ComponentError::ManagedByGlobalBorrow {
+                    type_name: type_name::<T>(),
+                })
+            }
+            _ => Ok(()),
         }
     }
 
     /// Borrow the component of type `T`, if it exists
     ///
     /// Panics if the component is already uniquely borrowed from another entity with the same
-    /// components.
+    /// components, or if `T` is managed by this entity's associated [`GlobalBorrow`] (use
+    /// [`try_get_with`](Self::try_get_with) instead).
     pub fn get<T: Component>(&self) -> Option<Ref<'a, T>> {
-        Some(unsafe { Ref::new(self.archetype?, self.index).ok()? })
+        match self.try_get() {
+            Ok(borrow) => Some(borrow),
+            Err(ComponentError::CannotBorrow { type_name }) => {
+                panic!("{} already borrowed", type_name)
+            }
+            Err(ComponentError::ManagedByGlobalBorrow { type_name }) => {
+                panic!(
+                    "{} is managed by a GlobalBorrow; use try_get_with instead of get",
+                    type_name
+                )
+            }
+            Err(_) => None,
+        }
     }
 
     /// Uniquely borrow the component of type `T`, if it exists
     ///
-    /// Panics if the component is already borrowed from another entity with the same components.
+    /// Panics if the component is already borrowed from another entity with the same components,
+    /// or if `T` is managed by this entity's associated [`GlobalBorrow`] (use
+    /// [`try_get_mut_with`](Self::try_get_mut_with) instead).
     pub fn get_mut<T: Component>(&self) -> Option<RefMut<'a, T>> {
-        Some(unsafe { RefMut::new(self.archetype?, self.index).ok()? })
+        match self.try_get_mut() {
+            Ok(borrow) => Some(borrow),
+            Err(ComponentError::CannotBorrow { type_name }) => {
+                panic!("{} already borrowed", type_name)
+            }
+            Err(ComponentError::ManagedByGlobalBorrow { type_name }) => {
+                panic!(
+                    "{} is managed by a GlobalBorrow; use try_get_mut_with instead of get_mut",
+                    type_name
+                )
+            }
+            Err(_) => None,
+        }
+    }
+
+    /// Borrow the component of type `T`, if it exists
+    ///
+    /// Unlike [`get`](Self::get), never panics: a conflicting borrow is reported as
+    /// [`ComponentError::CannotBorrow`] rather than unwinding, so callers can recover from
+    /// transient contention instead of crashing. Still returns `Err` rather than borrowing if `T`
+    /// is managed by this entity's associated [`GlobalBorrow`]; call
+    /// [`try_get_with`](Self::try_get_with) for such a type instead.
+    pub fn try_get<T: Component>(&self) -> Result<Ref<'a, T>, ComponentError> {
+        self.check_not_globally_managed::<T>()?;
+        let archetype = self.archetype.ok_or(ComponentError::MissingComponent {
+            type_name: type_name::<T>(),
+        })?;
+        unsafe { Ref::new(archetype, self.index) }
+    }
+
+    /// Uniquely borrow the component of type `T`, if it exists
+    ///
+    /// Unlike [`get_mut`](Self::get_mut), never panics: a conflicting borrow is reported as
+    /// [`ComponentError::CannotBorrow`] rather than unwinding, so callers can recover from
+    /// transient contention instead of crashing. Still returns `Err` rather than borrowing if `T`
+    /// is managed by this entity's associated [`GlobalBorrow`]; call
+    /// [`try_get_mut_with`](Self::try_get_mut_with) for such a type instead.
+    pub fn try_get_mut<T: Component>(&self) -> Result<RefMut<'a, T>, ComponentError> {
+        self.check_not_globally_managed::<T>()?;
+        let archetype = self.archetype.ok_or(ComponentError::MissingComponent {
+            type_name: type_name::<T>(),
+        })?;
+        unsafe { RefMut::new(archetype, self.index) }
+    }
+
+    /// Borrow the component of type `T` through this entity's associated [`GlobalBorrow`]
+    /// instead of its own archetype
+    ///
+    /// See [`GlobalBorrow`] for the coarser-but-cheaper conflict semantics this opts into: this
+    /// conflicts with an outstanding borrow of `T` on *any* entity sharing the same
+    /// `GlobalBorrow`, not just ones in the same archetype. It can never alias a borrow taken
+    /// through [`get`](Self::get)/[`get_mut`](Self::get_mut), since those panic for any type
+    /// registered here rather than touching the archetype's own flag for it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this entity has no associated `GlobalBorrow` (see
+    /// [`new_with_global`](Self::new_with_global)), or if `T` was never registered with it via
+    /// [`GlobalBorrow::register`]; unlike the rest of this `try_`-prefixed API, neither is a
+    /// recoverable `Result` because there's no flag to even check for contention against.
+    pub fn try_get_with<T: Component>(&self) -> Result<Ref<'a, T>, ComponentError> {
+        let archetype = self.archetype.ok_or(ComponentError::MissingComponent {
+            type_name: type_name::<T>(),
+        })?;
+        let global = self
+            .global
+            .expect("entity has no associated GlobalBorrow");
+        unsafe { Ref::new_global(global, archetype, self.index) }
+    }
+
+    /// Uniquely borrow the component of type `T` through this entity's associated
+    /// [`GlobalBorrow`] instead of its own archetype
+    ///
+    /// See [`GlobalBorrow`] for the coarser-but-cheaper conflict semantics this opts into, and
+    /// [`try_get_with`](Self::try_get_with) for why this can never alias a plain
+    /// [`get`](Self::get)/[`get_mut`](Self::get_mut) borrow.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this entity has no associated `GlobalBorrow`, or if `T` was never registered
+    /// with it via [`GlobalBorrow::register`]; see [`try_get_with`](Self::try_get_with).
+    pub fn try_get_mut_with<T: Component>(&self) -> Result<RefMut<'a, T>, ComponentError> {
+        let archetype = self.archetype.ok_or(ComponentError::MissingComponent {
+            type_name: type_name::<T>(),
+        })?;
+        let global = self
+            .global
+            .expect("entity has no associated GlobalBorrow");
+        unsafe { RefMut::new_global(global, archetype, self.index) }
+    }
+
+    /// Borrow the component with the given `TypeId`, if it exists, without knowing its concrete
+    /// type
+    ///
+    /// Combine with [`component_types`](Self::component_types) and a [`ComponentRegistry`] to
+    /// dispatch per-component logic, e.g. for serialization or reflection, without a
+    /// compile-time list of component types.
+    ///
+    /// Returns `None` if the component is missing, if it's uniquely borrowed, or if `ty` is
+    /// managed by this entity's associated [`GlobalBorrow`].
+    pub fn get_dyn(&self, ty: TypeId) -> Option<DynRef<'a>> {
+        if self.global.is_some_and(|global| global.flags.contains_key(&ty)) {
+            return None;
+        }
+        unsafe { DynRef::new(self.archetype?, ty, self.index) }
+    }
+
+    /// Uniquely borrow the component with the given `TypeId`, if it exists, without knowing its
+    /// concrete type
+    ///
+    /// Returns `None` if the component is missing, if it's already borrowed, or if `ty` is
+    /// managed by this entity's associated [`GlobalBorrow`].
+    pub fn get_dyn_mut(&self, ty: TypeId) -> Option<DynRefMut<'a>> {
+        if self.global.is_some_and(|global| global.flags.contains_key(&ty)) {
+            return None;
+        }
+        unsafe { DynRefMut::new(self.archetype?, ty, self.index) }
+    }
+
+    /// Move the component of type `T` out of the entity, if it exists, for the guard's lifetime
+    ///
+    /// Acquires a unique borrow and returns a [`TakenRef`] that owns the component's value; the
+    /// value is written back into the entity when the guard drops.
+    ///
+    /// Panics if the component is already borrowed from another entity with the same components,
+    /// for the same reason [`get_mut`](Self::get_mut) does: a `None` here should mean "no such
+    /// component", not "try again later". Also panics if `T` is managed by this entity's
+    /// associated [`GlobalBorrow`], same as [`get_mut`](Self::get_mut) does, since `take_scoped`
+    /// acquires the archetype's own flag just like it.
+    pub fn take_scoped<T: Component>(&self) -> Option<TakenRef<'a, T>> {
+        if let Err(ComponentError::ManagedByGlobalBorrow { type_name }) =
+            self.check_not_globally_managed::<T>()
+        {
+            panic!(
+                "{} is managed by a GlobalBorrow; use try_get_mut_with instead of take_scoped",
+                type_name
+            )
+        }
+        match unsafe { TakenRef::new(self.archetype?, self.index) } {
+            Ok(taken) => Some(taken),
+            Err(ComponentError::CannotBorrow { type_name }) => {
+                panic!("{} already borrowed", type_name)
+            }
+            Err(_) => None,
+        }
     }
 
     /// Enumerate the types of the entity's components
@@ -192,3 +758,233 @@ impl<'a> EntityRef<'a> {
 
 unsafe impl<'a> Send for EntityRef<'a> {}
 unsafe impl<'a> Sync for EntityRef<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn global_borrow_conflicts_across_archetypes() {
+        let mut global = GlobalBorrow::new();
+        global.register::<i32>();
+        let a = Archetype::for_test(1i32);
+        let b = Archetype::for_test(2i32);
+        let entity_a = unsafe { EntityRef::new_with_global(&a, 0, &global) };
+        let entity_b = unsafe { EntityRef::new_with_global(&b, 0, &global) };
+
+        let _unique = entity_a.try_get_mut_with::<i32>().unwrap();
+        assert!(matches!(
+            entity_b.try_get_with::<i32>(),
+            Err(ComponentError::CannotBorrow { .. })
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "not registered")]
+    fn global_borrow_panics_for_unregistered_type() {
+        let global = GlobalBorrow::new();
+        let archetype = Archetype::for_test(1i32);
+        let entity = unsafe { EntityRef::new_with_global(&archetype, 0, &global) };
+
+        let _ = entity.try_get_with::<i32>();
+    }
+
+    #[test]
+    #[should_panic(expected = "no associated GlobalBorrow")]
+    fn try_get_with_panics_without_an_associated_global_borrow() {
+        let archetype = Archetype::for_test(1i32);
+        let entity = unsafe { EntityRef::new(&archetype, 0) };
+
+        let _ = entity.try_get_with::<i32>();
+    }
+
+    #[test]
+    #[should_panic(expected = "managed by a GlobalBorrow")]
+    fn get_panics_for_a_globally_managed_type() {
+        let mut global = GlobalBorrow::new();
+        global.register::<i32>();
+        let archetype = Archetype::for_test(1i32);
+        let entity = unsafe { EntityRef::new_with_global(&archetype, 0, &global) };
+
+        entity.get::<i32>();
+    }
+
+    #[test]
+    #[should_panic(expected = "managed by a GlobalBorrow")]
+    fn get_mut_panics_for_a_globally_managed_type() {
+        let mut global = GlobalBorrow::new();
+        global.register::<i32>();
+        let archetype = Archetype::for_test(1i32);
+        let entity = unsafe { EntityRef::new_with_global(&archetype, 0, &global) };
+
+        entity.get_mut::<i32>();
+    }
+
+    #[test]
+    #[should_panic(expected = "managed by a GlobalBorrow")]
+    fn take_scoped_panics_for_a_globally_managed_type() {
+        let mut global = GlobalBorrow::new();
+        global.register::<i32>();
+        let archetype = Archetype::for_test(1i32);
+        let entity = unsafe { EntityRef::new_with_global(&archetype, 0, &global) };
+
+        entity.take_scoped::<i32>();
+    }
+
+    #[test]
+    fn global_and_archetype_borrows_never_alias_the_same_component() {
+        // This is the soundness property the panics above exist for: a type opted into a
+        // `GlobalBorrow` is reachable *only* through the `_with` accessors, so there's no way for
+        // a `get_mut` and a `try_get_mut_with` to both succeed for the same slot at once.
+        let mut global = GlobalBorrow::new();
+        global.register::<i32>();
+        let archetype = Archetype::for_test(1i32);
+        let entity = unsafe { EntityRef::new_with_global(&archetype, 0, &global) };
+
+        let _unique = entity.try_get_mut_with::<i32>().unwrap();
+        assert!(matches!(
+            entity.try_get_mut::<i32>(),
+            Err(ComponentError::ManagedByGlobalBorrow { .. })
+        ));
+    }
+
+    #[test]
+    fn try_get_reports_missing_component_without_panicking() {
+        let archetype = Archetype::for_test(1i32);
+        let entity = unsafe { EntityRef::new(&archetype, 0) };
+
+        assert_eq!(
+            entity.try_get::<f32>().err(),
+            Some(ComponentError::MissingComponent {
+                type_name: core::any::type_name::<f32>()
+            })
+        );
+    }
+
+    #[test]
+    fn try_get_mut_reports_conflicting_borrow_without_panicking() {
+        let archetype = Archetype::for_test(1i32);
+        let entity = unsafe { EntityRef::new(&archetype, 0) };
+
+        let _held = entity.get_mut::<i32>().unwrap();
+        assert!(matches!(
+            entity.try_get_mut::<i32>(),
+            Err(ComponentError::CannotBorrow { .. })
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn get_mut_still_panics_on_conflicting_borrow() {
+        let archetype = Archetype::for_test(1i32);
+        let entity = unsafe { EntityRef::new(&archetype, 0) };
+
+        let _held = entity.get::<i32>().unwrap();
+        entity.get_mut::<i32>().unwrap();
+    }
+
+    #[test]
+    fn get_dyn_reads_the_right_bytes() {
+        let archetype = Archetype::for_test(7u64);
+        let entity = unsafe { EntityRef::new(&archetype, 0) };
+
+        let component = entity.get_dyn(TypeId::of::<u64>()).unwrap();
+        assert_eq!(component.layout(), Layout::new::<u64>());
+        let value = unsafe { component.as_ptr().cast::<u64>().as_ref() };
+        assert_eq!(*value, 7);
+    }
+
+    #[test]
+    fn get_dyn_is_none_for_unknown_type() {
+        let archetype = Archetype::for_test(7u64);
+        let entity = unsafe { EntityRef::new(&archetype, 0) };
+
+        assert!(entity.get_dyn(TypeId::of::<f32>()).is_none());
+    }
+
+    #[test]
+    fn get_dyn_mut_conflicts_with_get_dyn() {
+        let archetype = Archetype::for_test(7u64);
+        let entity = unsafe { EntityRef::new(&archetype, 0) };
+
+        let _shared = entity.get_dyn(TypeId::of::<u64>()).unwrap();
+        assert!(entity.get_dyn_mut(TypeId::of::<u64>()).is_none());
+    }
+
+    #[test]
+    fn component_registry_round_trips_a_handler() {
+        let mut registry: ComponentRegistry<fn(NonNull<u8>) -> i32> = ComponentRegistry::new();
+        registry.register::<i32>(|ptr| unsafe { *ptr.cast::<i32>().as_ref() });
+
+        let archetype = Archetype::for_test(11i32);
+        let entity = unsafe { EntityRef::new(&archetype, 0) };
+        let mut seen = Vec::new();
+        for ty in entity.component_types() {
+            if let Some(handler) = registry.get(ty) {
+                let component = entity.get_dyn(ty).unwrap();
+                seen.push(handler(component.as_ptr()));
+            }
+        }
+        assert_eq!(seen, [11]);
+    }
+
+    #[test]
+    fn take_scoped_replaces_and_restores_on_drop() {
+        let archetype = Archetype::for_test(String::from("old"));
+        let entity = unsafe { EntityRef::new(&archetype, 0) };
+
+        {
+            let mut taken = entity.take_scoped::<String>().unwrap();
+            assert_eq!(*taken, "old");
+            *taken = String::from("new");
+        }
+
+        assert_eq!(*entity.get::<String>().unwrap(), "new");
+    }
+
+    #[test]
+    fn take_scoped_restores_the_slot_even_on_panic_during_hold() {
+        let archetype = Archetype::for_test(5i32);
+        let entity = unsafe { EntityRef::new(&archetype, 0) };
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let mut taken = entity.take_scoped::<i32>().unwrap();
+            *taken = 99;
+            panic!("simulated failure mid-transition");
+        }));
+        assert!(result.is_err());
+
+        // The guard's Drop ran during unwind and wrote the (replaced) value back; the slot is
+        // never left uninitialized, and the unique borrow was released along with it.
+        assert_eq!(*entity.get::<i32>().unwrap(), 99);
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn take_scoped_panics_on_conflicting_borrow() {
+        let archetype = Archetype::for_test(1i32);
+        let entity = unsafe { EntityRef::new(&archetype, 0) };
+
+        let _held = entity.get::<i32>().unwrap();
+        entity.take_scoped::<i32>();
+    }
+
+    #[test]
+    fn global_borrow_does_not_conflict_with_an_unassociated_entitys_archetype_borrow() {
+        // A `GlobalBorrow` only overrides access for the `EntityRef`s it's explicitly attached
+        // to via `new_with_global`; an entity with no `global` of its own still goes through its
+        // own archetype flag for `i32`, even though some other `GlobalBorrow` elsewhere has `i32`
+        // registered.
+        let mut global = GlobalBorrow::new();
+        global.register::<i32>();
+        let archetype = Archetype::for_test(1i32);
+        let entity = unsafe { EntityRef::new(&archetype, 0) };
+
+        let _own = entity.get::<i32>().unwrap();
+
+        let archetype_with_global = Archetype::for_test(2i32);
+        let entity_with_global =
+            unsafe { EntityRef::new_with_global(&archetype_with_global, 0, &global) };
+        assert!(entity_with_global.try_get_with::<i32>().is_ok());
+    }
+}